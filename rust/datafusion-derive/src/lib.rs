@@ -0,0 +1,239 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `#[derive(FromRecordBatch)]`, matching struct fields to `RecordBatch` columns
+//! by identifier.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+
+/// Derive `datafusion::datasource::FromRecordBatch` for a plain struct, matching
+/// each field to a column of the same name and downcasting it to the array type
+/// implied by the field's declared type (`Option<T>` columns tolerate nulls,
+/// everything else requires the column to be non-null).
+#[proc_macro_derive(FromRecordBatch)]
+pub fn derive_from_record_batch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input,
+                    "FromRecordBatch can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input,
+                "FromRecordBatch can only be derived for structs",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let column_lookups = fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_name_str = field_name.to_string();
+        let (is_option, inner_type) = unwrap_option(&field.ty);
+        let array_type = array_type_for(inner_type);
+        let is_string = is_string_type(inner_type);
+
+        let missing_column_err = format!(
+            "Column '{}' was not found in the RecordBatch",
+            field_name_str
+        );
+        let wrong_type_err = format!(
+            "Column '{}' has a DataType incompatible with the declared field type",
+            field_name_str
+        );
+        let null_value_err = format!(
+            "Column '{}' holds a null, but the declared field type is not an Option",
+            field_name_str
+        );
+
+        // `StringArray::value` returns `&str` borrowed from the batch; owned
+        // `String` fields need it converted before it can outlive the batch
+        let raw_value = if is_string {
+            quote! { typed_array.value(row).to_string() }
+        } else {
+            quote! { typed_array.value(row) }
+        };
+
+        let value_expr = if is_option {
+            quote! {
+                if typed_array.is_null(row) {
+                    None
+                } else {
+                    Some(#raw_value)
+                }
+            }
+        } else {
+            quote! {
+                {
+                    if typed_array.is_null(row) {
+                        return Err(datafusion::error::ExecutionError::General(
+                            #null_value_err.to_string(),
+                        ));
+                    }
+                    #raw_value
+                }
+            }
+        };
+
+        quote! {
+            let column_index = batch.schema().index_of(#field_name_str).map_err(|_| {
+                datafusion::error::ExecutionError::General(#missing_column_err.to_string())
+            })?;
+            let column = batch.column(column_index);
+            let typed_array = column
+                .as_any()
+                .downcast_ref::<#array_type>()
+                .ok_or_else(|| {
+                    datafusion::error::ExecutionError::General(#wrong_type_err.to_string())
+                })?;
+            let #field_name = #value_expr;
+        }
+    });
+
+    let field_names = fields.iter().map(|field| field.ident.as_ref().unwrap());
+
+    let expanded = quote! {
+        impl datafusion::datasource::FromRecordBatch for #name {
+            fn from_batch(
+                batch: &datafusion::arrow::record_batch::RecordBatch,
+            ) -> datafusion::error::Result<Vec<Self>> {
+                use datafusion::arrow::array::Array;
+
+                let mut rows = Vec::with_capacity(batch.num_rows());
+                for row in 0..batch.num_rows() {
+                    #(#column_lookups)*
+                    rows.push(Self {
+                        #(#field_names),*
+                    });
+                }
+                Ok(rows)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// If `ty` is `Option<T>`, return `(true, T)`; otherwise `(false, ty)`
+fn unwrap_option(ty: &Type) -> (bool, &Type) {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return (true, inner);
+                    }
+                }
+            }
+        }
+    }
+    (false, ty)
+}
+
+/// `true` if `ty` is `String`, the one supported field type whose array's
+/// `value(row)` does not already return an owned value
+fn is_string_type(ty: &Type) -> bool {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            return segment.ident == "String";
+        }
+    }
+    false
+}
+
+/// Map a field's Rust type to the Arrow array type `from_batch` should downcast
+/// its matching column into
+fn array_type_for(ty: &Type) -> proc_macro2::TokenStream {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            return match segment.ident.to_string().as_str() {
+                "i64" => quote! { datafusion::arrow::array::Int64Array },
+                "f64" => quote! { datafusion::arrow::array::Float64Array },
+                "bool" => quote! { datafusion::arrow::array::BooleanArray },
+                "String" => quote! { datafusion::arrow::array::StringArray },
+                other => quote! { compile_error!(concat!("Unsupported FromRecordBatch field type: ", #other)) },
+            };
+        }
+    }
+    quote! { compile_error!("Unsupported FromRecordBatch field type") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ty(src: &str) -> Type {
+        syn::parse_str(src).unwrap()
+    }
+
+    #[test]
+    fn unwrap_option_strips_option_wrapper() {
+        let (is_option, inner) = unwrap_option(&parse_ty("Option<i64>"));
+        assert!(is_option);
+        assert_eq!(quote!(#inner).to_string(), "i64");
+    }
+
+    #[test]
+    fn unwrap_option_passes_through_plain_types() {
+        let ty = parse_ty("i64");
+        let (is_option, inner) = unwrap_option(&ty);
+        assert!(!is_option);
+        assert_eq!(quote!(#inner).to_string(), "i64");
+    }
+
+    #[test]
+    fn is_string_type_only_matches_string() {
+        assert!(is_string_type(&parse_ty("String")));
+        assert!(!is_string_type(&parse_ty("i64")));
+        assert!(!is_string_type(&parse_ty("bool")));
+    }
+
+    #[test]
+    fn array_type_for_maps_each_supported_scalar() {
+        assert_eq!(
+            array_type_for(&parse_ty("i64")).to_string(),
+            quote!(datafusion::arrow::array::Int64Array).to_string()
+        );
+        assert_eq!(
+            array_type_for(&parse_ty("f64")).to_string(),
+            quote!(datafusion::arrow::array::Float64Array).to_string()
+        );
+        assert_eq!(
+            array_type_for(&parse_ty("bool")).to_string(),
+            quote!(datafusion::arrow::array::BooleanArray).to_string()
+        );
+        assert_eq!(
+            array_type_for(&parse_ty("String")).to_string(),
+            quote!(datafusion::arrow::array::StringArray).to_string()
+        );
+    }
+}
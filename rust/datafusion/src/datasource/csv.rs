@@ -0,0 +1,149 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! CSV data source
+
+use std::sync::{Arc, Mutex};
+
+use arrow::csv;
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+
+use crate::datasource::datasource::{BatchIterator, ScanOptions, ScanResult, TableProvider};
+use crate::datasource::object_store::{LocalFileSystem, ObjectStore};
+use crate::error::{ExecutionError, Result};
+
+/// Table backed by a single CSV file, read through an `ObjectStore` so the file
+/// may live on local disk, in memory, or in a remote blob store
+pub struct CsvFile {
+    object_store: Arc<dyn ObjectStore>,
+    path: String,
+    schema: Arc<Schema>,
+    has_header: bool,
+    delimiter: u8,
+}
+
+impl CsvFile {
+    /// Open `path` on the local filesystem, inferring the schema from the first
+    /// `max_read_records` lines
+    pub fn try_new(path: &str, has_header: bool, max_read_records: usize) -> Result<Self> {
+        Self::try_new_with_store(
+            Arc::new(LocalFileSystem),
+            path,
+            has_header,
+            max_read_records,
+        )
+    }
+
+    /// Open `path` through `object_store`, inferring the schema from the first
+    /// `max_read_records` lines
+    pub fn try_new_with_store(
+        object_store: Arc<dyn ObjectStore>,
+        path: &str,
+        has_header: bool,
+        max_read_records: usize,
+    ) -> Result<Self> {
+        let reader = object_store.open(path)?;
+        let (schema, _) = csv::reader::infer_file_schema(reader, b',', Some(max_read_records), has_header)?;
+        Ok(Self {
+            object_store,
+            path: path.to_string(),
+            schema: Arc::new(schema),
+            has_header,
+            delimiter: b',',
+        })
+    }
+
+    /// Use an explicit schema instead of inferring one
+    pub fn try_new_with_schema(
+        object_store: Arc<dyn ObjectStore>,
+        path: &str,
+        schema: Arc<Schema>,
+        has_header: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            object_store,
+            path: path.to_string(),
+            schema,
+            has_header,
+            delimiter: b',',
+        })
+    }
+}
+
+impl TableProvider for CsvFile {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn scan(&self, options: &ScanOptions, batch_size: usize) -> Result<Vec<ScanResult>> {
+        // CSV has no column-chunk statistics to prune against, so `options.filters`
+        // is ignored; only the projection is honored
+        let reader = self.object_store.open(&self.path)?;
+        let iterator: ScanResult = Arc::new(Mutex::new(CsvBatchIterator::try_new(
+            reader,
+            self.schema.clone(),
+            self.has_header,
+            self.delimiter,
+            &options.projection,
+            batch_size,
+        )?));
+        Ok(vec![iterator])
+    }
+}
+
+/// `BatchIterator` over the rows of a single CSV partition
+pub struct CsvBatchIterator {
+    schema: Arc<Schema>,
+    reader: csv::Reader<Box<dyn std::io::Read + Send>>,
+}
+
+impl CsvBatchIterator {
+    /// Wrap `reader` to decode CSV rows into `RecordBatch`es of up to `batch_size`
+    /// rows, optionally restricted to `projection`
+    pub fn try_new(
+        reader: Box<dyn std::io::Read + Send>,
+        schema: Arc<Schema>,
+        has_header: bool,
+        delimiter: u8,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let reader = csv::Reader::new(
+            reader,
+            schema.clone(),
+            has_header,
+            Some(delimiter),
+            batch_size,
+            None,
+            projection.clone(),
+        );
+        let schema = match projection {
+            Some(columns) => Arc::new(Schema::new(
+                columns.iter().map(|i| schema.field(*i).clone()).collect(),
+            )),
+            None => schema,
+        };
+        Ok(Self { schema, reader })
+    }
+}
+
+impl BatchIterator for CsvBatchIterator {
+    fn next(&mut self) -> Result<Option<RecordBatch>> {
+        self.reader.next().transpose().map_err(ExecutionError::from)
+    }
+}
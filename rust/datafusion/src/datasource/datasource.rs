@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Data source traits shared by all table providers (CSV, Parquet, in-memory, ...)
+
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+
+use crate::error::Result;
+
+/// Source table which can be queried through a `TableProvider::scan` call
+pub trait TableProvider {
+    /// Get the schema for this table
+    fn schema(&self) -> Arc<Schema>;
+
+    /// Perform a scan of the table, returning one `ScanResult` per partition that
+    /// can be iterated to completion independently of the others. `options`
+    /// restricts which columns are materialized and, where the source supports
+    /// it, allows whole chunks of data to be skipped without being decoded; a
+    /// source that cannot act on `options.filters` is still expected to honor
+    /// `options.projection`.
+    fn scan(&self, options: &ScanOptions, batch_size: usize) -> Result<Vec<ScanResult>>;
+}
+
+/// Projection and filter pushdown passed into `TableProvider::scan`. Sources that
+/// cannot prune on `filters` (e.g. `MemTable`, `CsvFile`) simply ignore them and
+/// return unfiltered batches; the `filters` are re-applied afterwards by the
+/// physical query execution pipeline regardless, so this is purely an
+/// optimization and never changes correctness.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Indices, into `TableProvider::schema()`, of the columns to materialize
+    pub projection: Option<Vec<usize>>,
+    /// Conjunctive (AND-ed) predicates that may be used to prune data the source
+    /// can prove would not match
+    pub filters: Vec<Predicate>,
+}
+
+impl ScanOptions {
+    /// Convenience constructor for a pure projection, no filters
+    pub fn with_projection(projection: Option<Vec<usize>>) -> Self {
+        Self {
+            projection,
+            filters: vec![],
+        }
+    }
+}
+
+/// A single predicate of the form `column <op> literal`
+#[derive(Debug, Clone)]
+pub struct Predicate {
+    /// Name of the column being compared
+    pub column: String,
+    /// Comparison operator
+    pub op: Operator,
+    /// Literal value being compared against
+    pub value: ScalarValue,
+}
+
+/// Comparison operator supported by simple column-vs-literal predicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `=`
+    Eq,
+    /// `<`
+    Lt,
+    /// `<=`
+    LtEq,
+    /// `>`
+    Gt,
+    /// `>=`
+    GtEq,
+}
+
+/// A literal value appearing on the right-hand side of a `Predicate`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarValue {
+    /// 64-bit signed integer literal
+    Int64(i64),
+    /// 64-bit floating point literal
+    Float64(f64),
+    /// UTF-8 string literal
+    Utf8(String),
+    /// Boolean literal
+    Boolean(bool),
+}
+
+/// Iterator over `RecordBatch`es produced by scanning a single partition of a table
+pub trait BatchIterator: Send + Sync {
+    /// Get the next batch from the iterator, `None` when the iterator is exhausted
+    fn next(&mut self) -> Result<Option<RecordBatch>>;
+}
+
+/// A single partition of a `TableProvider::scan`, shared so it can be driven from
+/// another thread (e.g. by the physical query execution pipeline)
+pub type ScanResult = Arc<Mutex<dyn BatchIterator + Send + Sync>>;
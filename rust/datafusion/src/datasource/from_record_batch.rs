@@ -0,0 +1,206 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Typed deserialization of `RecordBatch`es into Rust structs, so callers that
+//! don't want to work with Arrow arrays directly can collect a scan as `Vec<T>`.
+
+use arrow::record_batch::RecordBatch;
+
+use crate::datasource::datasource::ScanResult;
+use crate::error::Result;
+
+/// Implemented for types that can be materialized, one row per struct instance,
+/// from a `RecordBatch`. Normally derived with `#[derive(FromRecordBatch)]`,
+/// which matches struct fields to columns by identifier and downcasts each
+/// column to the array type implied by the field's declared Rust type.
+pub trait FromRecordBatch: Sized {
+    /// Build one `Self` per row of `batch`
+    fn from_batch(batch: &RecordBatch) -> Result<Vec<Self>>;
+}
+
+/// Adapts a `ScanResult` partition into an iterator of typed rows, draining the
+/// underlying `RecordBatch`es as needed
+pub struct TypedBatchIterator<T: FromRecordBatch> {
+    scan: ScanResult,
+    buffer: std::vec::IntoIter<T>,
+}
+
+impl<T: FromRecordBatch> TypedBatchIterator<T> {
+    /// Wrap a `ScanResult` partition, deserializing each `RecordBatch` as it is
+    /// pulled from the scan
+    pub fn new(scan: ScanResult) -> Self {
+        Self {
+            scan,
+            buffer: vec![].into_iter(),
+        }
+    }
+}
+
+impl<T: FromRecordBatch> Iterator for TypedBatchIterator<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.buffer.next() {
+                return Some(Ok(row));
+            }
+            let mut scan = self.scan.lock().unwrap();
+            match scan.next() {
+                Ok(Some(batch)) => match T::from_batch(&batch) {
+                    Ok(rows) => {
+                        drop(scan);
+                        self.buffer = rows.into_iter();
+                    }
+                    Err(e) => return Some(Err(e)),
+                },
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use arrow::array::{Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use super::*;
+    use crate::datasource::datasource::BatchIterator;
+    use crate::error::ExecutionError;
+
+    #[derive(Debug, PartialEq)]
+    struct Row {
+        id: i64,
+        name: String,
+    }
+
+    // Mirrors the body `#[derive(FromRecordBatch)]` generates for `Row`
+    impl FromRecordBatch for Row {
+        fn from_batch(batch: &RecordBatch) -> Result<Vec<Self>> {
+            let id_index = batch.schema().index_of("id").map_err(|_| {
+                ExecutionError::General("Column 'id' was not found in the RecordBatch".to_string())
+            })?;
+            let id_array = batch
+                .column(id_index)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| {
+                    ExecutionError::General(
+                        "Column 'id' has a DataType incompatible with the declared field type"
+                            .to_string(),
+                    )
+                })?;
+            let name_index = batch.schema().index_of("name").map_err(|_| {
+                ExecutionError::General("Column 'name' was not found in the RecordBatch".to_string())
+            })?;
+            let name_array = batch
+                .column(name_index)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| {
+                    ExecutionError::General(
+                        "Column 'name' has a DataType incompatible with the declared field type"
+                            .to_string(),
+                    )
+                })?;
+            Ok((0..batch.num_rows())
+                .map(|row| Row {
+                    id: id_array.value(row),
+                    name: name_array.value(row).to_string(),
+                })
+                .collect())
+        }
+    }
+
+    fn row_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// `BatchIterator` that yields a fixed list of batches then ends, for driving
+    /// `TypedBatchIterator` in tests without a real `TableProvider`
+    struct FixedBatches(std::vec::IntoIter<RecordBatch>);
+
+    impl BatchIterator for FixedBatches {
+        fn next(&mut self) -> Result<Option<RecordBatch>> {
+            Ok(self.0.next())
+        }
+    }
+
+    fn scan_of(batches: Vec<RecordBatch>) -> ScanResult {
+        Arc::new(Mutex::new(FixedBatches(batches.into_iter())))
+    }
+
+    #[test]
+    fn collects_rows_across_multiple_batches() {
+        let scan = scan_of(vec![row_batch(), row_batch()]);
+        let rows: Result<Vec<Row>> = TypedBatchIterator::new(scan).collect();
+        let rows = rows.unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], Row { id: 1, name: "a".to_string() });
+        assert_eq!(rows[3], Row { id: 2, name: "b".to_string() });
+    }
+
+    #[test]
+    fn surfaces_missing_column_error() {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap();
+        let scan = scan_of(vec![batch]);
+        let mut iter: TypedBatchIterator<Row> = TypedBatchIterator::new(scan);
+        let err = iter.next().unwrap().unwrap_err();
+        match err {
+            ExecutionError::General(msg) => assert!(msg.contains("'name'")),
+            other => panic!("expected ExecutionError::General, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn surfaces_wrong_type_error() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["not-an-int"])),
+                Arc::new(StringArray::from(vec!["a"])),
+            ],
+        )
+        .unwrap();
+        let scan = scan_of(vec![batch]);
+        let mut iter: TypedBatchIterator<Row> = TypedBatchIterator::new(scan);
+        let err = iter.next().unwrap().unwrap_err();
+        match err {
+            ExecutionError::General(msg) => assert!(msg.contains("'id'")),
+            other => panic!("expected ExecutionError::General, got {:?}", other),
+        }
+    }
+}
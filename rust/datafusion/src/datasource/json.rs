@@ -0,0 +1,374 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Newline-delimited JSON data source
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::Value;
+
+use crate::datasource::datasource::{BatchIterator, ScanOptions, ScanResult, TableProvider};
+use crate::datasource::object_store::{LocalFileSystem, ObjectStore};
+use crate::error::{ExecutionError, Result};
+
+/// Table backed by a single newline-delimited JSON file (one object per line),
+/// read through an `ObjectStore` so the file may live on local disk, in memory, or
+/// in a remote blob store
+pub struct JsonFile {
+    object_store: Arc<dyn ObjectStore>,
+    path: String,
+    schema: Arc<Schema>,
+}
+
+impl JsonFile {
+    /// Open `path` on the local filesystem, inferring the schema from the first
+    /// `max_read_records` lines
+    pub fn try_new(path: &str, max_read_records: usize) -> Result<Self> {
+        Self::try_new_with_store(Arc::new(LocalFileSystem), path, max_read_records)
+    }
+
+    /// Open `path` through `object_store`, inferring the schema from the first
+    /// `max_read_records` lines
+    pub fn try_new_with_store(
+        object_store: Arc<dyn ObjectStore>,
+        path: &str,
+        max_read_records: usize,
+    ) -> Result<Self> {
+        let reader = BufReader::new(object_store.open(path)?);
+        let schema = infer_json_schema(reader, max_read_records)?;
+        Ok(Self {
+            object_store,
+            path: path.to_string(),
+            schema: Arc::new(schema),
+        })
+    }
+
+    /// Use an explicit schema instead of inferring one
+    pub fn try_new_with_schema(
+        object_store: Arc<dyn ObjectStore>,
+        path: &str,
+        schema: Arc<Schema>,
+    ) -> Result<Self> {
+        Ok(Self {
+            object_store,
+            path: path.to_string(),
+            schema,
+        })
+    }
+}
+
+impl TableProvider for JsonFile {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn scan(&self, options: &ScanOptions, batch_size: usize) -> Result<Vec<ScanResult>> {
+        // newline-delimited JSON has no sub-file statistics to prune against, so
+        // `options.filters` is ignored; only the projection is honored
+        let reader = BufReader::new(self.object_store.open(&self.path)?);
+        let iterator: ScanResult = Arc::new(Mutex::new(JsonBatchIterator::try_new(
+            reader,
+            self.schema.clone(),
+            &options.projection,
+            batch_size,
+        )?));
+        Ok(vec![iterator])
+    }
+}
+
+/// Infer a `Schema` by reading up to `max_read_records` lines of newline-delimited
+/// JSON, widening each field's `DataType` as wider types are observed
+pub fn infer_json_schema<R: Read>(reader: BufReader<R>, max_read_records: usize) -> Result<Schema> {
+    let mut fields: BTreeMap<String, Option<DataType>> = BTreeMap::new();
+    let mut records_read = 0;
+    for line in reader.lines() {
+        if records_read >= max_read_records {
+            break;
+        }
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(line)
+            .map_err(|e| ExecutionError::General(format!("Error parsing JSON line: {}", e)))?;
+        let object = value.as_object().ok_or_else(|| {
+            ExecutionError::General("Expected each JSON line to be an object".to_string())
+        })?;
+        for (name, value) in object {
+            match infer_data_type(value) {
+                Some(inferred) => {
+                    let entry = fields.entry(name.clone()).or_insert(None);
+                    *entry = Some(match entry.take() {
+                        Some(existing) => widen_data_type(&existing, &inferred),
+                        None => inferred,
+                    });
+                }
+                // a `null` carries no type information of its own, so it must not
+                // downgrade a type inferred from another record (before or after
+                // it) to `Utf8`; still record that the field is present, defaulting
+                // to `Utf8` below in case every sampled record happened to have it null
+                None => {
+                    fields.entry(name.clone()).or_insert(None);
+                }
+            }
+        }
+        records_read += 1;
+    }
+    Ok(Schema::new(
+        fields
+            .into_iter()
+            .map(|(name, data_type)| Field::new(&name, data_type.unwrap_or(DataType::Utf8), true))
+            .collect(),
+    ))
+}
+
+/// Infer the `DataType` implied by a single JSON value, or `None` for `null`
+/// since it carries no type information on its own. Arrays and objects are
+/// inferred as `Utf8` rather than a nested Arrow type: `build_column` stores them
+/// as their JSON-encoded string representation, so a file with nested fields
+/// (routine in real-world JSON logs) can still be scanned instead of erroring.
+fn infer_data_type(value: &Value) -> Option<DataType> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some(DataType::Boolean),
+        Value::Number(n) => Some(if n.is_i64() || n.is_u64() {
+            DataType::Int64
+        } else {
+            DataType::Float64
+        }),
+        Value::String(_) | Value::Array(_) | Value::Object(_) => Some(DataType::Utf8),
+    }
+}
+
+/// Widen two inferred types to one that can represent values of both, following
+/// the same `Int64 < Float64 < Utf8` promotion CSV inference already uses
+fn widen_data_type(a: &DataType, b: &DataType) -> DataType {
+    match (a, b) {
+        (a, b) if a == b => a.clone(),
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            DataType::Float64
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+/// `BatchIterator` over the lines of a single newline-delimited JSON partition
+pub struct JsonBatchIterator {
+    schema: Arc<Schema>,
+    lines: std::io::Lines<BufReader<Box<dyn Read + Send>>>,
+    batch_size: usize,
+}
+
+impl JsonBatchIterator {
+    /// Wrap `reader` to decode JSON lines into `RecordBatch`es of up to
+    /// `batch_size` rows, optionally restricted to `projection`
+    pub fn try_new(
+        reader: BufReader<Box<dyn Read + Send>>,
+        schema: Arc<Schema>,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let schema = match projection {
+            Some(columns) => Arc::new(Schema::new(
+                columns.iter().map(|i| schema.field(*i).clone()).collect(),
+            )),
+            None => schema,
+        };
+        Ok(Self {
+            schema,
+            lines: reader.lines(),
+            batch_size,
+        })
+    }
+}
+
+impl BatchIterator for JsonBatchIterator {
+    fn next(&mut self) -> Result<Option<RecordBatch>> {
+        let mut rows = vec![];
+        while rows.len() < self.batch_size {
+            match self.lines.next() {
+                Some(line) => {
+                    let line = line?;
+                    let line = line.trim().to_string();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let value: Value = serde_json::from_str(&line).map_err(|e| {
+                        ExecutionError::General(format!("Error parsing JSON line: {}", e))
+                    })?;
+                    rows.push(value);
+                }
+                None => break,
+            }
+        }
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| build_column(field, &rows))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+}
+
+fn build_column(field: &Field, rows: &[Value]) -> Result<ArrayRef> {
+    let values: Vec<Option<&Value>> = rows
+        .iter()
+        .map(|row| row.as_object().and_then(|o| o.get(field.name())))
+        .collect();
+    Ok(match field.data_type() {
+        DataType::Int64 => Arc::new(Int64Array::from(
+            values.iter().map(|v| v.and_then(Value::as_i64)).collect::<Vec<_>>(),
+        )),
+        DataType::Float64 => Arc::new(Float64Array::from(
+            values.iter().map(|v| v.and_then(Value::as_f64)).collect::<Vec<_>>(),
+        )),
+        DataType::Boolean => Arc::new(BooleanArray::from(
+            values.iter().map(|v| v.and_then(Value::as_bool)).collect::<Vec<_>>(),
+        )),
+        DataType::Utf8 => Arc::new(StringArray::from(
+            values
+                .iter()
+                .map(|v| v.and_then(json_scalar_as_utf8))
+                .collect::<Vec<_>>(),
+        )),
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "JSON data source does not yet materialize nested type {:?}",
+                other
+            )))
+        }
+    })
+}
+
+/// Render a JSON value as the `Utf8` string `infer_data_type` promised, whether
+/// it fell back to `Utf8` because a field's values disagreed on type (e.g. one
+/// record has `"count": 3`, another `"count": "unknown"`) or because the value
+/// is an array/object, which this source stores as its JSON-encoded text rather
+/// than a nested Arrow type. Strings pass through unchanged; everything else is
+/// formatted the way `Value`'s `Display` impl already renders it, so inference
+/// and materialization agree on what "widen to Utf8" means. `null` stays absent
+/// rather than becoming the literal string `"null"`.
+fn json_scalar_as_utf8(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Object(_) => {
+            Some(value.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+
+    fn schema_of(lines: &str) -> Schema {
+        infer_json_schema(BufReader::new(lines.as_bytes()), 100).unwrap()
+    }
+
+    #[test]
+    fn infers_scalar_types() {
+        let schema = schema_of(
+            "{\"id\": 1, \"score\": 1.5, \"active\": true, \"name\": \"a\"}\n",
+        );
+        assert_eq!(schema.field_with_name("id").unwrap().data_type(), &DataType::Int64);
+        assert_eq!(
+            schema.field_with_name("score").unwrap().data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(
+            schema.field_with_name("active").unwrap().data_type(),
+            &DataType::Boolean
+        );
+        assert_eq!(schema.field_with_name("name").unwrap().data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn widens_int_and_float_across_lines() {
+        let schema = schema_of("{\"value\": 1}\n{\"value\": 1.5}\n");
+        assert_eq!(
+            schema.field_with_name("value").unwrap().data_type(),
+            &DataType::Float64
+        );
+    }
+
+    #[test]
+    fn widens_int_and_string_to_utf8() {
+        let schema = schema_of("{\"value\": 1}\n{\"value\": \"unknown\"}\n");
+        assert_eq!(schema.field_with_name("value").unwrap().data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn infers_nested_values_as_json_encoded_utf8() {
+        let schema = schema_of("{\"point\": {\"x\": 1, \"y\": 2}, \"tags\": [\"a\", \"b\"]}\n");
+        assert_eq!(schema.field_with_name("point").unwrap().data_type(), &DataType::Utf8);
+        assert_eq!(schema.field_with_name("tags").unwrap().data_type(), &DataType::Utf8);
+
+        let field = Field::new("point", DataType::Utf8, true);
+        let rows = vec![serde_json::json!({"point": {"x": 1, "y": 2}})];
+        let column = build_column(&field, &rows).unwrap();
+        let strings = column.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(strings.value(0), "{\"x\":1,\"y\":2}");
+    }
+
+    #[test]
+    fn a_null_sample_does_not_downgrade_an_otherwise_consistent_type() {
+        let schema = schema_of("{\"code\": null}\n{\"code\": 5}\n");
+        assert_eq!(schema.field_with_name("code").unwrap().data_type(), &DataType::Int64);
+    }
+
+    #[test]
+    fn a_field_that_is_always_null_defaults_to_utf8() {
+        let schema = schema_of("{\"code\": null}\n");
+        assert_eq!(schema.field_with_name("code").unwrap().data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn build_column_stringifies_widened_non_string_values() {
+        let field = Field::new("value", DataType::Utf8, true);
+        let rows = vec![
+            serde_json::json!({"value": 1}),
+            serde_json::json!({"value": "unknown"}),
+            serde_json::json!({"value": true}),
+        ];
+        let column = build_column(&field, &rows).unwrap();
+        let strings = column.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(strings.value(0), "1");
+        assert_eq!(strings.value(1), "unknown");
+        assert_eq!(strings.value(2), "true");
+    }
+
+    #[test]
+    fn build_column_leaves_missing_field_null() {
+        let field = Field::new("value", DataType::Int64, true);
+        let rows = vec![serde_json::json!({"other": 1})];
+        let column = build_column(&field, &rows).unwrap();
+        let ints = column.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert!(ints.is_null(0));
+    }
+}
@@ -0,0 +1,133 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! In-memory table backed by `RecordBatch`es already held in memory
+
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+
+use crate::datasource::datasource::{BatchIterator, ScanOptions, ScanResult, TableProvider};
+use crate::error::{ExecutionError, Result};
+
+/// In-memory table, where each partition is backed by a vector of `RecordBatch`
+pub struct MemTable {
+    schema: Arc<Schema>,
+    batches: Vec<Vec<RecordBatch>>,
+}
+
+impl MemTable {
+    /// Create a new in-memory table from a schema and a list of partitions, each
+    /// partition being a list of `RecordBatch`
+    pub fn new(schema: Arc<Schema>, partitions: Vec<Vec<RecordBatch>>) -> Result<Self> {
+        for partition in &partitions {
+            for batch in partition {
+                if batch.schema().as_ref() != schema.as_ref() {
+                    return Err(ExecutionError::General(
+                        "Mismatch between schema and batches".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            schema,
+            batches: partitions,
+        })
+    }
+}
+
+impl TableProvider for MemTable {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn scan(&self, options: &ScanOptions, _batch_size: usize) -> Result<Vec<ScanResult>> {
+        // row-group style pruning on `options.filters` is not applicable to an
+        // in-memory table; only the projection is honored
+        self.batches
+            .iter()
+            .map(|partition| {
+                let iterator: ScanResult = Arc::new(Mutex::new(MemBatchIterator::try_new(
+                    partition.clone(),
+                    &options.projection,
+                )?));
+                Ok(iterator)
+            })
+            .collect()
+    }
+}
+
+/// Iterator over the batches belonging to a single in-memory partition
+pub struct MemBatchIterator {
+    schema: Arc<Schema>,
+    batches: Vec<RecordBatch>,
+    index: usize,
+}
+
+impl MemBatchIterator {
+    /// Create a new `MemBatchIterator`, optionally projecting each batch down to a
+    /// subset of its columns
+    pub fn try_new(batches: Vec<RecordBatch>, projection: &Option<Vec<usize>>) -> Result<Self> {
+        let full_schema = match batches.first() {
+            Some(batch) => batch.schema().clone(),
+            None => {
+                return Err(ExecutionError::General(
+                    "Cannot infer schema from empty partition".to_string(),
+                ))
+            }
+        };
+        let (schema, batches) = match projection {
+            Some(columns) => {
+                let projected_schema = Arc::new(Schema::new(
+                    columns
+                        .iter()
+                        .map(|i| full_schema.field(*i).clone())
+                        .collect(),
+                ));
+                let batches = batches
+                    .into_iter()
+                    .map(|batch| {
+                        RecordBatch::try_new(
+                            projected_schema.clone(),
+                            columns.iter().map(|i| batch.column(*i).clone()).collect(),
+                        )
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(ExecutionError::from)?;
+                (projected_schema, batches)
+            }
+            None => (full_schema, batches),
+        };
+        Ok(Self {
+            schema,
+            batches,
+            index: 0,
+        })
+    }
+}
+
+impl BatchIterator for MemBatchIterator {
+    fn next(&mut self) -> Result<Option<RecordBatch>> {
+        Ok(if self.index < self.batches.len() {
+            self.index += 1;
+            Some(self.batches[self.index - 1].clone())
+        } else {
+            None
+        })
+    }
+}
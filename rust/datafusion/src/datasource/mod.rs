@@ -21,12 +21,30 @@
 pub mod csv;
 
 pub mod datasource;
+pub mod from_record_batch;
+pub mod json;
 pub mod memory;
+pub mod object_store;
+
+#[cfg(feature = "s3")]
+pub mod object_store_s3;
 
 #[cfg(not(target_arch="wasm32"))]
 pub mod parquet;
 
+#[cfg(not(target_arch="wasm32"))]
+pub mod partitioned;
+
 #[cfg(not(target_arch="wasm32"))]
 pub use self::csv::{CsvBatchIterator, CsvFile};
 pub use self::datasource::{ScanResult, TableProvider};
+pub use self::from_record_batch::{FromRecordBatch, TypedBatchIterator};
+pub use self::json::{JsonBatchIterator, JsonFile};
 pub use self::memory::{MemBatchIterator, MemTable};
+pub use self::object_store::{FileMeta, InMemory, LocalFileSystem, ObjectStore, ObjectStoreRegistry};
+
+#[cfg(not(target_arch="wasm32"))]
+pub use self::partitioned::{FileFormat, PartitionedTable};
+
+#[cfg(feature = "s3")]
+pub use self::object_store_s3::S3FileSystem;
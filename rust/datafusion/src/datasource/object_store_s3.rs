@@ -0,0 +1,129 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `ObjectStore` backed by an S3 bucket. Gated behind the `s3` feature so that
+//! consumers who only scan local/in-memory tables don't pull in a networking stack.
+//!
+//! `ObjectStore` is a synchronous trait, but the underlying `rusoto_s3` client is
+//! built on Tokio. Rather than calling `futures::executor::block_on` directly
+//! (which panics with "no reactor running" unless the caller happens to already
+//! be inside a compatible Tokio context, and would otherwise stall whichever
+//! executor thread called it if that context is a multi-threaded runtime), each
+//! `S3FileSystem` owns a dedicated single-threaded runtime and drives every
+//! request on it with `Runtime::block_on`. `list`/`open` can therefore be called
+//! from any synchronous code, whether or not another Tokio runtime exists in the
+//! process; calling them from inside an async task panics, since Tokio does not
+//! allow blocking on a runtime from a thread that is already driving one. Each
+//! call blocks the calling thread until its requests complete.
+
+use std::io::Read;
+
+use futures::TryStreamExt;
+use rusoto_core::Region;
+use rusoto_s3::{ListObjectsV2Request, S3Client, S3};
+use tokio::runtime::{Builder, Runtime};
+
+use crate::datasource::object_store::{FileMeta, ObjectStore};
+use crate::error::{ExecutionError, Result};
+
+/// `ObjectStore` implementation backed by a single S3 bucket, reached in the
+/// region taken from the standard AWS environment/config unless overridden with
+/// `with_region`
+pub struct S3FileSystem {
+    bucket: String,
+    client: S3Client,
+    runtime: Runtime,
+}
+
+impl S3FileSystem {
+    /// Create a store scoped to `bucket`, using the region configured via the
+    /// standard AWS environment/credentials chain
+    pub fn new(bucket: impl Into<String>) -> Result<Self> {
+        Self::with_region(bucket, Region::default())
+    }
+
+    /// Create a store scoped to `bucket` in a specific region
+    pub fn with_region(bucket: impl Into<String>, region: Region) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ExecutionError::General(format!("Failed to start S3 runtime: {}", e)))?;
+        Ok(Self {
+            bucket: bucket.into(),
+            client: S3Client::new(region),
+            runtime,
+        })
+    }
+}
+
+impl ObjectStore for S3FileSystem {
+    fn list(&self, prefix: &str) -> Result<Vec<FileMeta>> {
+        let mut files = vec![];
+        let mut continuation_token = None;
+        loop {
+            let request = ListObjectsV2Request {
+                bucket: self.bucket.clone(),
+                prefix: Some(prefix.to_string()),
+                continuation_token: continuation_token.clone(),
+                ..Default::default()
+            };
+            let response = self
+                .runtime
+                .block_on(self.client.list_objects_v2(request))
+                .map_err(|e| ExecutionError::General(format!("S3 list_objects_v2 failed: {}", e)))?;
+            for object in response.contents.unwrap_or_default() {
+                if let Some(key) = object.key {
+                    files.push(FileMeta {
+                        path: format!("s3://{}/{}", self.bucket, key),
+                        size: object.size.unwrap_or(0) as u64,
+                    });
+                }
+            }
+            continuation_token = response.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(files)
+    }
+
+    fn open(&self, path: &str) -> Result<Box<dyn Read + Send>> {
+        let key = path.trim_start_matches(&format!("s3://{}/", self.bucket));
+        let request = rusoto_s3::GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_string(),
+            ..Default::default()
+        };
+        let bytes = self.runtime.block_on(async {
+            let response = self
+                .client
+                .get_object(request)
+                .await
+                .map_err(|e| ExecutionError::General(format!("S3 get_object failed: {}", e)))?;
+            let body = response.body.ok_or_else(|| {
+                ExecutionError::General(format!("S3 object '{}' has no body", path))
+            })?;
+            body.map_ok(|b| b.to_vec())
+                .try_concat()
+                .await
+                .map_err(|e| {
+                    ExecutionError::General(format!("Failed reading S3 object '{}': {}", path, e))
+                })
+        })?;
+        Ok(Box::new(std::io::Cursor::new(bytes)))
+    }
+}
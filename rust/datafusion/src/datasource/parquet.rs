@@ -0,0 +1,427 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Parquet data source
+
+use std::io::{Cursor, Read};
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::{ArrowReader, ParquetFileArrowReader};
+use parquet::file::metadata::ParquetMetaData;
+use parquet::file::reader::{FileReader, RowGroupReader, SerializedFileReader};
+use parquet::file::statistics::Statistics;
+
+use crate::datasource::datasource::{
+    BatchIterator, Operator, ScalarValue, ScanOptions, ScanResult, TableProvider,
+};
+use crate::datasource::object_store::{LocalFileSystem, ObjectStore};
+use crate::error::{ExecutionError, Result};
+
+/// Table backed by a single Parquet file, read through an `ObjectStore` so the
+/// file may live on local disk, in memory, or in a remote blob store
+pub struct ParquetTable {
+    object_store: Arc<dyn ObjectStore>,
+    path: String,
+    schema: Arc<Schema>,
+}
+
+impl ParquetTable {
+    /// Open `path` on the local filesystem and read its embedded schema
+    pub fn try_new(path: &str) -> Result<Self> {
+        Self::try_new_with_store(Arc::new(LocalFileSystem), path)
+    }
+
+    /// Open `path` through `object_store` and read its embedded schema
+    pub fn try_new_with_store(object_store: Arc<dyn ObjectStore>, path: &str) -> Result<Self> {
+        let bytes = read_to_end(object_store.open(path)?)?;
+        let file_reader = Arc::new(SerializedFileReader::new(Cursor::new(bytes))?);
+        let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
+        let schema = Arc::new(arrow_reader.get_schema()?);
+        Ok(Self {
+            object_store,
+            path: path.to_string(),
+            schema,
+        })
+    }
+}
+
+impl TableProvider for ParquetTable {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn scan(&self, options: &ScanOptions, batch_size: usize) -> Result<Vec<ScanResult>> {
+        let bytes = read_to_end(self.object_store.open(&self.path)?)?;
+        let file_reader: Arc<dyn FileReader> =
+            Arc::new(SerializedFileReader::new(Cursor::new(bytes))?);
+        let file_reader = prune_row_groups(file_reader, &self.schema, &options.filters)?;
+        let iterator: ScanResult = Arc::new(Mutex::new(ParquetBatchIterator::try_new(
+            file_reader,
+            &options.projection,
+            batch_size,
+        )?));
+        Ok(vec![iterator])
+    }
+}
+
+fn read_to_end(mut reader: Box<dyn Read + Send>) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Drop row groups that `filters` prove cannot contain a matching row, using each
+/// column chunk's min/max statistics. A row group is kept unless at least one
+/// filter can be evaluated against its statistics and provably excludes it;
+/// columns without statistics, or predicates this function doesn't understand,
+/// are treated as "might match" so pruning never drops rows that should be read.
+fn prune_row_groups(
+    file_reader: Arc<dyn FileReader>,
+    schema: &Schema,
+    filters: &[crate::datasource::datasource::Predicate],
+) -> Result<Arc<dyn FileReader>> {
+    if filters.is_empty() {
+        return Ok(file_reader);
+    }
+    let metadata = file_reader.metadata();
+    let mut selected = vec![];
+    for i in 0..metadata.num_row_groups() {
+        let row_group = metadata.row_group(i);
+        let excluded = filters.iter().any(|filter| {
+            let column_index = match schema.index_of(&filter.column) {
+                Ok(index) => index,
+                Err(_) => return false,
+            };
+            match row_group.column(column_index).statistics() {
+                Some(statistics) => row_group_excluded(filter, statistics),
+                None => false,
+            }
+        });
+        if !excluded {
+            selected.push(i);
+        }
+    }
+    let pruned_metadata = ParquetMetaData::new(
+        metadata.file_metadata().clone(),
+        selected.iter().map(|i| metadata.row_group(*i).clone()).collect(),
+    );
+    Ok(Arc::new(PrunedFileReader {
+        inner: file_reader,
+        metadata: pruned_metadata,
+        selected,
+    }))
+}
+
+/// `true` if `statistics`' min/max range proves `filter` cannot match any row in
+/// that row group
+fn row_group_excluded(
+    filter: &crate::datasource::datasource::Predicate,
+    statistics: &Statistics,
+) -> bool {
+    let (min, max) = match (statistics_as_i64(statistics), &filter.value) {
+        (Some(range), ScalarValue::Int64(_)) => range,
+        _ => return false,
+    };
+    let v = match &filter.value {
+        ScalarValue::Int64(v) => *v,
+        _ => return false,
+    };
+    match filter.op {
+        Operator::Gt => max <= v,
+        Operator::GtEq => max < v,
+        Operator::Lt => min >= v,
+        Operator::LtEq => min > v,
+        Operator::Eq => v < min || v > max,
+    }
+}
+
+fn statistics_as_i64(statistics: &Statistics) -> Option<(i64, i64)> {
+    match statistics {
+        Statistics::Int32(s) if s.has_min_max_set() => Some((*s.min() as i64, *s.max() as i64)),
+        Statistics::Int64(s) if s.has_min_max_set() => Some((*s.min(), *s.max())),
+        _ => None,
+    }
+}
+
+/// `FileReader` that exposes only the row groups selected by `prune_row_groups`,
+/// delegating the actual column-chunk decoding to `inner`
+struct PrunedFileReader {
+    inner: Arc<dyn FileReader>,
+    metadata: ParquetMetaData,
+    /// Row group ordinal in `inner` for each row group exposed by this reader, in
+    /// order, so `get_row_group(i)` can be forwarded to the right underlying group
+    selected: Vec<usize>,
+}
+
+impl FileReader for PrunedFileReader {
+    fn metadata(&self) -> &ParquetMetaData {
+        &self.metadata
+    }
+
+    fn num_row_groups(&self) -> usize {
+        self.selected.len()
+    }
+
+    fn get_row_group(&self, i: usize) -> parquet::errors::Result<Box<dyn RowGroupReader + '_>> {
+        self.inner.get_row_group(self.selected[i])
+    }
+
+    fn get_row_iter(
+        &self,
+        projection: Option<parquet::schema::types::Type>,
+    ) -> parquet::errors::Result<parquet::record::reader::RowIter> {
+        // `ParquetBatchIterator` only ever drives this reader through
+        // `get_row_group`/`metadata` (via `ParquetFileArrowReader`), which respects
+        // `selected`. There is no public API to build a `RowIter` over an
+        // arbitrary subset of row groups, so when groups have actually been
+        // pruned, refuse rather than silently hand back a superset of rows the
+        // pruning predicate excluded.
+        if self.selected.len() == self.inner.num_row_groups() {
+            return self.inner.get_row_iter(projection);
+        }
+        Err(parquet::errors::ParquetError::General(
+            "get_row_iter is not supported on a FileReader with pruned row groups".to_string(),
+        ))
+    }
+}
+
+/// `BatchIterator` over the row groups of a single Parquet partition
+pub struct ParquetBatchIterator {
+    batch_reader: Box<dyn Iterator<Item = arrow::error::Result<RecordBatch>>>,
+}
+
+impl ParquetBatchIterator {
+    /// Build an iterator that decodes `file_reader` into `RecordBatch`es of up to
+    /// `batch_size` rows, optionally restricted to `projection`
+    pub fn try_new(
+        file_reader: Arc<dyn FileReader>,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
+        let batch_reader = match projection {
+            Some(columns) => arrow_reader.get_record_reader_by_columns(columns.clone(), batch_size)?,
+            None => arrow_reader.get_record_reader(batch_size)?,
+        };
+        Ok(Self {
+            batch_reader: Box::new(batch_reader),
+        })
+    }
+}
+
+impl BatchIterator for ParquetBatchIterator {
+    fn next(&mut self) -> Result<Option<RecordBatch>> {
+        self.batch_reader
+            .next()
+            .transpose()
+            .map_err(ExecutionError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, Int64Array};
+    use parquet::column::writer::ColumnWriter;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::{FileWriter, SerializedFileWriter};
+    use parquet::schema::parser::parse_message_type;
+
+    use crate::datasource::datasource::Predicate;
+    use crate::datasource::object_store::InMemory;
+
+    use super::*;
+
+    fn int64_stats(min: i64, max: i64) -> Statistics {
+        Statistics::int64(Some(min), Some(max), None, 0, false)
+    }
+
+    /// Write a single-column (`a BIGINT`) Parquet file with one row group per
+    /// entry of `row_groups`, each containing the given `i64` values
+    fn write_int64_parquet(row_groups: &[Vec<i64>]) -> Vec<u8> {
+        let schema = Arc::new(
+            parse_message_type("message schema { REQUIRED INT64 a; }").unwrap(),
+        );
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut bytes = vec![];
+        {
+            let mut writer =
+                SerializedFileWriter::new(Cursor::new(&mut bytes), schema, props).unwrap();
+            for values in row_groups {
+                let mut row_group_writer = writer.next_row_group().unwrap();
+                while let Some(mut column_writer) = row_group_writer.next_column().unwrap() {
+                    match column_writer {
+                        ColumnWriter::Int64ColumnWriter(ref mut typed) => {
+                            typed.write_batch(values, None, None).unwrap();
+                        }
+                        _ => unreachable!("schema has a single INT64 column"),
+                    }
+                    row_group_writer.close_column(column_writer).unwrap();
+                }
+                writer.close_row_group(row_group_writer).unwrap();
+            }
+            writer.close().unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn scan_prunes_row_groups_and_returns_only_the_matching_rows() {
+        let bytes = write_int64_parquet(&[vec![1, 2, 3], vec![100, 101, 102]]);
+        let mut store = InMemory::new();
+        store.put("data.parquet", bytes);
+        let table = ParquetTable::try_new_with_store(Arc::new(store), "data.parquet").unwrap();
+
+        let options = ScanOptions {
+            projection: None,
+            filters: vec![Predicate {
+                column: "a".to_string(),
+                op: Operator::Gt,
+                value: ScalarValue::Int64(50),
+            }],
+        };
+        let partitions = table.scan(&options, 1024).unwrap();
+        assert_eq!(partitions.len(), 1);
+
+        let mut values = vec![];
+        let mut partition = partitions[0].lock().unwrap();
+        while let Some(batch) = partition.next().unwrap() {
+            let column = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+            values.extend((0..column.len()).map(|i| column.value(i)));
+        }
+        // the first row group (max 3) is pruned by `a > 50`; only the second
+        // row group's values are ever decoded
+        assert_eq!(values, vec![100, 101, 102]);
+    }
+
+    #[test]
+    fn scan_without_a_pruning_predicate_reads_every_row_group() {
+        let bytes = write_int64_parquet(&[vec![1, 2], vec![100, 101]]);
+        let mut store = InMemory::new();
+        store.put("data.parquet", bytes);
+        let table = ParquetTable::try_new_with_store(Arc::new(store), "data.parquet").unwrap();
+
+        let mut values = vec![];
+        let partition = table.scan(&ScanOptions::default(), 1024).unwrap().remove(0);
+        let mut partition = partition.lock().unwrap();
+        while let Some(batch) = partition.next().unwrap() {
+            let column = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+            values.extend((0..column.len()).map(|i| column.value(i)));
+        }
+        assert_eq!(values, vec![1, 2, 100, 101]);
+    }
+
+    #[test]
+    fn get_row_iter_refuses_once_row_groups_have_been_pruned() {
+        let bytes = write_int64_parquet(&[vec![1, 2, 3], vec![100, 101, 102]]);
+        let file_reader: Arc<dyn FileReader> =
+            Arc::new(SerializedFileReader::new(Cursor::new(bytes)).unwrap());
+        let metadata = file_reader.metadata();
+        let all_row_groups: Vec<_> = (0..metadata.num_row_groups())
+            .map(|i| metadata.row_group(i).clone())
+            .collect();
+
+        let unpruned = PrunedFileReader {
+            inner: file_reader.clone(),
+            metadata: ParquetMetaData::new(metadata.file_metadata().clone(), all_row_groups.clone()),
+            selected: (0..all_row_groups.len()).collect(),
+        };
+        assert!(unpruned.get_row_iter(None).is_ok());
+
+        let pruned = PrunedFileReader {
+            inner: file_reader,
+            metadata: ParquetMetaData::new(metadata.file_metadata().clone(), vec![all_row_groups[1].clone()]),
+            selected: vec![1],
+        };
+        assert!(pruned.get_row_iter(None).is_err());
+    }
+
+    fn predicate(op: Operator, value: i64) -> Predicate {
+        Predicate {
+            column: "a".to_string(),
+            op,
+            value: ScalarValue::Int64(value),
+        }
+    }
+
+    #[test]
+    fn statistics_as_i64_reads_int32_and_int64_ranges() {
+        let i32_stats = Statistics::int32(Some(1), Some(10), None, 0, false);
+        assert_eq!(statistics_as_i64(&i32_stats), Some((1, 10)));
+        assert_eq!(statistics_as_i64(&int64_stats(-5, 5)), Some((-5, 5)));
+    }
+
+    #[test]
+    fn statistics_as_i64_returns_none_without_min_max_set() {
+        let stats = Statistics::int64(None, None, None, 0, false);
+        assert_eq!(statistics_as_i64(&stats), None);
+    }
+
+    #[test]
+    fn row_group_excluded_ignores_predicates_on_non_int_scalars() {
+        let stats = int64_stats(0, 100);
+        let filter = Predicate {
+            column: "a".to_string(),
+            op: Operator::Eq,
+            value: ScalarValue::Utf8("x".to_string()),
+        };
+        assert!(!row_group_excluded(&filter, &stats));
+    }
+
+    #[test]
+    fn row_group_excluded_eq_boundary() {
+        let stats = int64_stats(10, 20);
+        // exactly at the boundaries, the row group might still match
+        assert!(!row_group_excluded(&predicate(Operator::Eq, 10), &stats));
+        assert!(!row_group_excluded(&predicate(Operator::Eq, 20), &stats));
+        // outside the range, it can never match
+        assert!(row_group_excluded(&predicate(Operator::Eq, 9), &stats));
+        assert!(row_group_excluded(&predicate(Operator::Eq, 21), &stats));
+    }
+
+    #[test]
+    fn row_group_excluded_gt_and_gteq_boundary() {
+        let stats = int64_stats(10, 20);
+        // `a > 20` can never match a row group whose max is 20
+        assert!(row_group_excluded(&predicate(Operator::Gt, 20), &stats));
+        // `a >= 20` can still match the row with value exactly 20
+        assert!(!row_group_excluded(&predicate(Operator::GtEq, 20), &stats));
+        // `a >= 21` can never match
+        assert!(row_group_excluded(&predicate(Operator::GtEq, 21), &stats));
+    }
+
+    #[test]
+    fn row_group_excluded_lt_and_lteq_boundary() {
+        let stats = int64_stats(10, 20);
+        // `a < 10` can never match a row group whose min is 10
+        assert!(row_group_excluded(&predicate(Operator::Lt, 10), &stats));
+        // `a <= 10` can still match the row with value exactly 10
+        assert!(!row_group_excluded(&predicate(Operator::LtEq, 10), &stats));
+        // `a <= 9` can never match
+        assert!(row_group_excluded(&predicate(Operator::LtEq, 9), &stats));
+    }
+
+    #[test]
+    fn row_group_excluded_defaults_to_might_match_without_statistics() {
+        // `prune_row_groups` never even calls `row_group_excluded` when a column
+        // chunk lacks statistics; this just pins that `statistics_as_i64` returning
+        // `None` (e.g. an unsupported statistics type) is treated as "might match"
+        let stats = Statistics::boolean(None, None, None, 0, false);
+        assert!(!row_group_excluded(&predicate(Operator::Eq, 1), &stats));
+    }
+}
@@ -0,0 +1,423 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hive-style partitioned directory data source, e.g. a tree laid out as
+//! `year=2021/month=11/*.parquet`, exposed as a single table with the path-encoded
+//! `year`/`month` segments materialized as extra columns.
+
+use std::sync::{Arc, Mutex};
+
+use arrow::array::{ArrayRef, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+
+use crate::datasource::csv::CsvFile;
+use crate::datasource::datasource::{BatchIterator, ScanOptions, ScanResult, TableProvider};
+use crate::datasource::object_store::ObjectStore;
+use crate::datasource::parquet::ParquetTable;
+use crate::error::{ExecutionError, Result};
+
+/// File format of the leaf files making up a `PartitionedTable`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Comma-separated values
+    Csv,
+    /// Apache Parquet
+    Parquet,
+}
+
+/// One leaf file discovered while walking a partitioned directory tree, together
+/// with the partition values its path encoded (in schema order)
+struct PartitionedFile {
+    path: String,
+    partition_values: Vec<String>,
+}
+
+/// `TableProvider` over a directory tree whose sub-directories encode partition
+/// columns as `key=value` segments (the "Hive" layout). Each leaf file becomes one
+/// partition of the scan, so execution can be parallelized across files; the
+/// partition columns are appended as constant columns to every batch read from
+/// that file.
+pub struct PartitionedTable {
+    file_schema: Arc<Schema>,
+    schema: Arc<Schema>,
+    partition_columns: Vec<String>,
+    format: FileFormat,
+    object_store: Arc<dyn ObjectStore>,
+    files: Vec<PartitionedFile>,
+}
+
+impl PartitionedTable {
+    /// Walk `root` through `object_store`, inferring the file schema from the
+    /// first file found and the partition columns from its directory structure
+    pub fn try_new(object_store: Arc<dyn ObjectStore>, root: &str, format: FileFormat) -> Result<Self> {
+        let mut files = vec![];
+        let mut partition_columns: Option<Vec<String>> = None;
+        for file in object_store.list(root)? {
+            let relative = file.path.trim_start_matches(root).trim_start_matches('/');
+            let mut segments: Vec<&str> = relative.split('/').collect();
+            // the last segment is the file name itself, not a partition directory
+            segments.pop();
+            let mut columns = vec![];
+            let mut values = vec![];
+            for segment in segments {
+                let mut parts = segment.splitn(2, '=');
+                let key = parts.next().ok_or_else(|| {
+                    ExecutionError::General(format!("Malformed partition segment '{}'", segment))
+                })?;
+                let value = parts.next().ok_or_else(|| {
+                    ExecutionError::General(format!(
+                        "Expected 'key=value' partition segment, got '{}'",
+                        segment
+                    ))
+                })?;
+                columns.push(key.to_string());
+                values.push(value.to_string());
+            }
+            match &partition_columns {
+                Some(expected) if expected != &columns => {
+                    return Err(ExecutionError::General(format!(
+                        "Inconsistent partition columns: expected {:?}, found {:?} at '{}'",
+                        expected, columns, file.path
+                    )))
+                }
+                Some(_) => {}
+                None => partition_columns = Some(columns),
+            }
+            files.push(PartitionedFile {
+                path: file.path,
+                partition_values: values,
+            });
+        }
+        let partition_columns = partition_columns.unwrap_or_default();
+        let first_file = files
+            .first()
+            .ok_or_else(|| ExecutionError::General(format!("No files found under '{}'", root)))?;
+        let file_schema = match format {
+            FileFormat::Csv => CsvFile::try_new_with_store(object_store.clone(), &first_file.path, true, 1000)?
+                .schema(),
+            FileFormat::Parquet => {
+                ParquetTable::try_new_with_store(object_store.clone(), &first_file.path)?.schema()
+            }
+        };
+        let mut fields = file_schema.fields().clone();
+        for partition_column in &partition_columns {
+            fields.push(Field::new(partition_column, DataType::Utf8, false));
+        }
+        Ok(Self {
+            file_schema,
+            schema: Arc::new(Schema::new(fields)),
+            partition_columns,
+            format,
+            object_store,
+            files,
+        })
+    }
+}
+
+impl TableProvider for PartitionedTable {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn scan(&self, options: &ScanOptions, batch_size: usize) -> Result<Vec<ScanResult>> {
+        let n_file_fields = self.file_schema.fields().len();
+        let file_projection = options.projection.as_ref().map(|columns| {
+            columns
+                .iter()
+                .copied()
+                .filter(|i| *i < n_file_fields)
+                .collect::<Vec<_>>()
+        });
+        // the caller's requested column order, defaulting to schema order when
+        // there is no projection; `PartitionColumnIterator` rebuilds its output in
+        // exactly this order, whether that interleaves file and partition columns
+        // or not, matching the guarantee `CsvBatchIterator`/`MemBatchIterator` make
+        let full_projection: Vec<usize> = options
+            .projection
+            .clone()
+            .unwrap_or_else(|| (0..self.schema.fields().len()).collect());
+        // predicates over partition columns are applied by the caller after the
+        // constant columns are appended below; only forward the ones that name a
+        // column the underlying file actually has, so a source like the parquet
+        // provider can still prune its own row groups
+        let file_filters = options
+            .filters
+            .iter()
+            .filter(|predicate| self.file_schema.index_of(&predicate.column).is_ok())
+            .cloned()
+            .collect();
+        let file_options = ScanOptions {
+            projection: file_projection,
+            filters: file_filters,
+        };
+        self.files
+            .iter()
+            .map(|file| {
+                let inner: Box<dyn TableProvider> = match self.format {
+                    FileFormat::Csv => Box::new(CsvFile::try_new_with_schema(
+                        self.object_store.clone(),
+                        &file.path,
+                        self.file_schema.clone(),
+                        true,
+                    )?),
+                    FileFormat::Parquet => Box::new(ParquetTable::try_new_with_store(
+                        self.object_store.clone(),
+                        &file.path,
+                    )?),
+                };
+                let mut partitions = inner.scan(&file_options, batch_size)?;
+                if partitions.len() != 1 {
+                    return Err(ExecutionError::General(
+                        "Expected a single-file table to produce exactly one partition".to_string(),
+                    ));
+                }
+                let column_sources = full_projection
+                    .iter()
+                    .map(|i| {
+                        if *i < n_file_fields {
+                            ProjectedColumn::File
+                        } else {
+                            let partition_index = *i - n_file_fields;
+                            ProjectedColumn::Partition {
+                                name: self.partition_columns[partition_index].clone(),
+                                value: file.partition_values[partition_index].clone(),
+                            }
+                        }
+                    })
+                    .collect();
+                let iterator: ScanResult = Arc::new(Mutex::new(PartitionColumnIterator::new(
+                    partitions.remove(0),
+                    column_sources,
+                )));
+                Ok(iterator)
+            })
+            .collect()
+    }
+}
+
+/// One output column of a `PartitionColumnIterator`: either the next column
+/// pulled from the inner file batch (in the order those columns appear there),
+/// or a partition column materialized as a constant value for every row
+enum ProjectedColumn {
+    /// The next not-yet-consumed column of the inner file batch
+    File,
+    /// A partition column with the value its file's path encoded
+    Partition { name: String, value: String },
+}
+
+/// Wraps an inner file's `ScanResult`, rebuilding each batch it produces into
+/// `column_sources` order so the output matches the caller's requested
+/// projection exactly, even when it interleaves file and partition columns
+struct PartitionColumnIterator {
+    inner: ScanResult,
+    column_sources: Vec<ProjectedColumn>,
+}
+
+impl PartitionColumnIterator {
+    fn new(inner: ScanResult, column_sources: Vec<ProjectedColumn>) -> Self {
+        Self {
+            inner,
+            column_sources,
+        }
+    }
+}
+
+impl BatchIterator for PartitionColumnIterator {
+    fn next(&mut self) -> Result<Option<RecordBatch>> {
+        let batch = match self.inner.lock().unwrap().next()? {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+        let mut fields = vec![];
+        let mut columns: Vec<ArrayRef> = vec![];
+        let mut next_file_column = 0;
+        for source in &self.column_sources {
+            match source {
+                ProjectedColumn::File => {
+                    fields.push(batch.schema().field(next_file_column).clone());
+                    columns.push(batch.column(next_file_column).clone());
+                    next_file_column += 1;
+                }
+                ProjectedColumn::Partition { name, value } => {
+                    fields.push(Field::new(name, DataType::Utf8, false));
+                    let repeated: StringArray =
+                        (0..batch.num_rows()).map(|_| Some(value.as_str())).collect();
+                    columns.push(Arc::new(repeated));
+                }
+            }
+        }
+        Ok(Some(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, Int64Array, StringArray as ArrowStringArray};
+
+    use super::*;
+    use crate::datasource::object_store::InMemory;
+
+    /// Build an `InMemory` store with two partitions of one CSV file each, under
+    /// `root/year=<y>/month=<m>/data.csv`
+    fn partitioned_csv_store() -> InMemory {
+        let mut store = InMemory::new();
+        store.put(
+            "root/year=2021/month=11/data.csv",
+            b"id,name\n1,a\n2,b\n".to_vec(),
+        );
+        store.put("root/year=2021/month=12/data.csv", b"id,name\n3,c\n".to_vec());
+        store
+    }
+
+    #[test]
+    fn discovers_partitions_and_appends_partition_columns_to_schema() {
+        let store = Arc::new(partitioned_csv_store());
+        let table = PartitionedTable::try_new(store, "root", FileFormat::Csv).unwrap();
+
+        let schema = table.schema();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["id", "name", "year", "month"]);
+        assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+        assert_eq!(schema.field(3).data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn rejects_inconsistent_partition_layouts() {
+        let mut store = InMemory::new();
+        store.put("root/year=2021/data.csv", b"id\n1\n".to_vec());
+        store.put("root/year=2021/month=11/data.csv", b"id\n2\n".to_vec());
+
+        let err = PartitionedTable::try_new(Arc::new(store), "root", FileFormat::Csv).unwrap_err();
+        match err {
+            ExecutionError::General(msg) => assert!(msg.contains("Inconsistent partition columns")),
+            other => panic!("expected ExecutionError::General, got {:?}", other),
+        }
+    }
+
+    /// Scan every partition and collect each row's column values, rendered as
+    /// strings, across all batches, sorted (by the leading `id` column) so the
+    /// result is deterministic regardless of file iteration order
+    fn scan_all(table: &PartitionedTable, options: &ScanOptions) -> Vec<Vec<String>> {
+        let mut rows = vec![];
+        for partition in table.scan(options, 1024).unwrap() {
+            loop {
+                let batch = match partition.lock().unwrap().next().unwrap() {
+                    Some(batch) => batch,
+                    None => break,
+                };
+                for row in 0..batch.num_rows() {
+                    let mut values = vec![];
+                    for column_index in 0..batch.num_columns() {
+                        let column = batch.column(column_index);
+                        let field = batch.schema().field(column_index).clone();
+                        let value = if field.data_type() == &DataType::Int64 {
+                            column
+                                .as_any()
+                                .downcast_ref::<Int64Array>()
+                                .unwrap()
+                                .value(row)
+                                .to_string()
+                        } else {
+                            column
+                                .as_any()
+                                .downcast_ref::<ArrowStringArray>()
+                                .unwrap()
+                                .value(row)
+                                .to_string()
+                        };
+                        values.push(value);
+                    }
+                    rows.push(values);
+                }
+            }
+        }
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn scan_without_projection_appends_every_partition_column() {
+        let store = Arc::new(partitioned_csv_store());
+        let table = PartitionedTable::try_new(store, "root", FileFormat::Csv).unwrap();
+
+        let rows = scan_all(&table, &ScanOptions::default());
+        assert_eq!(
+            rows,
+            vec![
+                vec!["1".to_string(), "a".to_string(), "2021".to_string(), "11".to_string()],
+                vec!["2".to_string(), "b".to_string(), "2021".to_string(), "11".to_string()],
+                vec!["3".to_string(), "c".to_string(), "2021".to_string(), "12".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_with_projection_only_appends_requested_partition_columns() {
+        let store = Arc::new(partitioned_csv_store());
+        let table = PartitionedTable::try_new(store, "root", FileFormat::Csv).unwrap();
+
+        // schema order is [id, name, year, month]; project away `name` and `year`,
+        // keeping only `id` and `month`
+        let options = ScanOptions::with_projection(Some(vec![0, 3]));
+        let rows = scan_all(&table, &options);
+        assert_eq!(
+            rows,
+            vec![
+                vec!["1".to_string(), "11".to_string()],
+                vec!["2".to_string(), "11".to_string()],
+                vec!["3".to_string(), "12".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_honors_the_requested_column_order_when_it_interleaves_file_and_partition_columns() {
+        let store = Arc::new(partitioned_csv_store());
+        let table = PartitionedTable::try_new(store, "root", FileFormat::Csv).unwrap();
+
+        // schema order is [id, name, year, month]; request `month` (a partition
+        // column) before `id` (a file column) to make sure the output isn't
+        // silently reordered to file-columns-first
+        let options = ScanOptions::with_projection(Some(vec![3, 0]));
+        let mut pairs = vec![];
+        for partition in table.scan(&options, 1024).unwrap() {
+            let batch = partition.lock().unwrap().next().unwrap().unwrap();
+            let names: Vec<&str> = batch.schema().fields().iter().map(|f| f.name().as_str()).collect();
+            assert_eq!(names, vec!["month", "id"]);
+
+            let month = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<ArrowStringArray>()
+                .unwrap();
+            let id = batch.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+            for row in 0..batch.num_rows() {
+                pairs.push((month.value(row).to_string(), id.value(row)));
+            }
+        }
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("11".to_string(), 1),
+                ("11".to_string(), 2),
+                ("12".to_string(), 3),
+            ]
+        );
+    }
+}